@@ -8,6 +8,28 @@ mod factory {
         call::{build_call, ExecutionInput, Selector},
         DefaultEnvironment,
     };
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    /// Mirrors `openbrush::contracts::psp34::Id` so it can be scale-encoded as a cross-contract
+    /// call argument without depending on openbrush.
+    #[derive(scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Id {
+        U8(u8),
+        U16(u16),
+        U32(u32),
+        U64(u64),
+        U128(u128),
+        Bytes(Vec<u8>),
+    }
+
+    /// Identifier of an access-control role, e.g. `ADMIN`.
+    pub type RoleType = u32;
+
+    /// May grant/revoke roles and set the mint price.
+    pub const ADMIN: RoleType = ink::selector_id!("ADMIN");
+
     #[ink(storage)]
     pub struct Factory {
         /// Contract owner
@@ -16,6 +38,36 @@ mod factory {
         token_contract: AccountId,
         /// Minting price. Caller must pay this price to mint one new token from Token contract
         price: Balance,
+        /// Id of the next token to mint, which is also the number of tokens minted so far
+        last_token_id: u64,
+        /// Maximum number of tokens that can ever be minted through this contract
+        max_supply: u64,
+        /// Roles granted to accounts
+        roles: Mapping<(RoleType, AccountId), ()>,
+    }
+
+    /// Emitted when tokens are minted through [`Minting::mint`].
+    #[ink(event)]
+    pub struct Minted {
+        /// Recipient of the minted tokens
+        #[ink(topic)]
+        to: AccountId,
+        /// Number of tokens minted
+        amount: Balance,
+        /// Amount of native token actually paid for the mint
+        paid: Balance,
+    }
+
+    /// Emitted when the mint price is changed through [`Minting::set_price`].
+    #[ink(event)]
+    pub struct PriceChanged {
+        /// Caller who changed the price
+        #[ink(topic)]
+        by: AccountId,
+        /// Previous price
+        old_price: Balance,
+        /// New price
+        new_price: Balance,
     }
 
     /// The Factory error types.
@@ -28,15 +80,25 @@ mod factory {
         NotOwner,
         /// Returned if the token contract account is not set during the contract creation.
         ContractNotSet,
+        /// Returned if minting `amount` tokens would exceed `max_supply`
+        CollectionFull,
+        /// Returned if `amount` is zero
+        BadMintValue,
+        /// Returned if multiplying price by amount, or advancing `last_token_id`, overflows
+        OverFlow,
+        /// Returned if the caller does not hold the role required for the call
+        MissingRole,
+        /// Returned if the cross-contract call to `PSP34::mint` failed
+        MintFailed,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
 
     #[ink::trait_definition]
     pub trait Minting {
-        /// Mint new tokens from Token contract
+        /// Mint `amount` new, sequentially-numbered tokens from the Token contract
         #[ink(message, payable)]
-        fn mint(&mut self, amount: Balance) -> Result<()>;
+        fn mint(&mut self, amount: u64) -> Result<()>;
 
         #[ink(message)]
         fn set_price(&mut self, price: Balance) -> Result<()>;
@@ -47,46 +109,124 @@ mod factory {
 
     impl Factory {
         #[ink(constructor)]
-        pub fn new(contract_acc: AccountId) -> Self {
+        pub fn new(contract_acc: AccountId, max_supply: u64) -> Self {
+            let caller = Self::env().caller();
+            let mut roles = Mapping::default();
+            roles.insert((ADMIN, caller), &());
             Self {
-                owner: Self::env().caller(),
+                owner: caller,
                 token_contract: contract_acc,
                 price: 1,
+                last_token_id: 0,
+                max_supply,
+                roles,
             }
         }
+
+        /// Returns whether `account` holds `role`.
+        #[ink(message)]
+        pub fn has_role(&self, role: RoleType, account: AccountId) -> bool {
+            self.roles.contains((role, account))
+        }
+
+        /// Grant `role` to `account`. Callable only by an `ADMIN`.
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: RoleType, account: AccountId) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            self.roles.insert((role, account), &());
+            Ok(())
+        }
+
+        /// Revoke `role` from `account`. Callable only by an `ADMIN`.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: RoleType, account: AccountId) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            self.roles.remove((role, account));
+            Ok(())
+        }
+
+        /// Withdraw `amount` of the contract's native token balance to the owner.
+        /// Callable by any `ADMIN`, not just `owner`, so this privilege can be
+        /// delegated the same way the rest of the role subsystem is.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            ensure!(
+                amount <= self.env().balance(),
+                Error::InsufficientBalance
+            );
+            self.env()
+                .transfer(self.owner, amount)
+                .map_err(|_| Error::InsufficientBalance)
+        }
+
+        /// Terminate the contract, sweeping the entire remaining balance to the owner.
+        /// Callable by any `ADMIN`, not just `owner`, so this privilege can be
+        /// delegated the same way the rest of the role subsystem is.
+        #[ink(message)]
+        pub fn terminate(&mut self) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            self.env().terminate_contract(self.owner)
+        }
     }
 
     impl Minting for Factory {
         #[ink(message, payable)]
-        fn mint(&mut self, amount: Balance) -> Result<()> {
+        fn mint(&mut self, amount: u64) -> Result<()> {
             let caller = self.env().caller();
+            ensure!(amount != 0, Error::BadMintValue);
             ensure!(
                 self.token_contract != AccountId::from([0x0; 32]),
                 Error::ContractNotSet
             );
+            let required = self
+                .price
+                .checked_mul(amount as Balance)
+                .ok_or(Error::OverFlow)?;
             ensure!(
-                self.price == self.env().transferred_value(),
+                self.env().transferred_value() == required,
                 Error::InsufficientBalance
             );
+            ensure!(
+                self.last_token_id.checked_add(amount).ok_or(Error::OverFlow)? <= self.max_supply,
+                Error::CollectionFull
+            );
 
-            let _mint_result = build_call::<DefaultEnvironment>()
-                .call(self.token_contract)
-                .gas_limit(5000000000)
-                .exec_input(
-                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP34::mint")))
-                        .push_arg(caller)
-                        .push_arg(amount),
-                )
-                .returns::<()>()
-                .try_invoke();
-            ink::env::debug_println!("mint_result: {:?}", _mint_result);
+            for i in 0..amount {
+                let id = Id::U64(self.last_token_id + i);
+                let mint_result = build_call::<DefaultEnvironment>()
+                    .call(self.token_contract)
+                    .gas_limit(5000000000)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP34::mint")))
+                            .push_arg(caller)
+                            .push_arg(id),
+                    )
+                    .returns::<()>()
+                    .try_invoke();
+                ink::env::debug_println!("mint_result: {:?}", mint_result);
+                ensure!(matches!(mint_result, Ok(Ok(()))), Error::MintFailed);
+            }
+            self.last_token_id += amount;
+
+            self.env().emit_event(Minted {
+                to: caller,
+                amount: amount as Balance,
+                paid: required,
+            });
             Ok(())
         }
 
         #[ink(message)]
         fn set_price(&mut self, price: Balance) -> Result<()> {
-            ensure!(self.env().caller() == self.owner, Error::NotOwner);
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            let old_price = self.price;
             self.price = price;
+            self.env().emit_event(PriceChanged {
+                by: self.env().caller(),
+                old_price,
+                new_price: price,
+            });
             Ok(())
         }
 
@@ -105,28 +245,145 @@ mod factory {
         /// Test error ContractNotSet.
         #[ink::test]
         fn contract_not_set_works() {
-            let mut factory = Factory::new([0x0; 32].into());
+            let mut factory = Factory::new([0x0; 32].into(), 1000);
             assert_eq!(factory.mint(50), Err(Error::ContractNotSet));
         }
 
         /// Test error InsufficientBalance.
         #[ink::test]
         fn insufficient_balance_works() {
-            let mut factory = Factory::new([0x1; 32].into());
+            let mut factory = Factory::new([0x1; 32].into(), 1000);
             assert_eq!(factory.mint(50), Err(Error::InsufficientBalance));
         }
 
+        /// Test withdrawing funds as the owner.
+        #[ink::test]
+        fn withdraw_works() {
+            let accounts = default_accounts();
+            let mut factory = Factory::new([0x0; 32].into(), 1000);
+            let contract_acc = ink::env::account_id::<Environment>();
+            test::set_account_balance::<Environment>(contract_acc, 100);
+
+            assert_eq!(factory.withdraw(200), Err(Error::InsufficientBalance));
+            assert!(factory.withdraw(100).is_ok());
+
+            // Non admin fails to withdraw
+            set_sender(accounts.bob);
+            assert_eq!(factory.withdraw(0), Err(Error::MissingRole));
+        }
+
+        /// Test that `terminate` sweeps the balance to the owner and removes the contract.
+        #[ink::test]
+        fn terminate_works() {
+            let accounts = default_accounts();
+            let mut factory = Factory::new([0x0; 32].into(), 1000);
+            let contract_acc = ink::env::account_id::<Environment>();
+            test::set_account_balance::<Environment>(contract_acc, 100);
+
+            test::assert_contract_termination::<Environment, _>(
+                move || {
+                    factory.terminate().unwrap();
+                },
+                accounts.alice,
+                100,
+            );
+        }
+
         /// Test setting price
         #[ink::test]
         fn set_price_works() {
             let accounts = default_accounts();
-            let mut factory = Factory::new([0x0; 32].into());
+            let mut factory = Factory::new([0x0; 32].into(), 1000);
             assert!(factory.set_price(100).is_ok());
             assert_eq!(factory.get_price(), 100);
 
-            // Non owner fails to set price
+            // Non admin fails to set price
             set_sender(accounts.bob);
-            assert_eq!(factory.set_price(100), Err(Error::NotOwner));
+            assert_eq!(factory.set_price(100), Err(Error::MissingRole));
+        }
+
+        /// Test granting and revoking roles.
+        #[ink::test]
+        fn grant_and_revoke_role_works() {
+            let accounts = default_accounts();
+            let mut factory = Factory::new([0x0; 32].into(), 1000);
+            assert!(factory.has_role(ADMIN, accounts.alice));
+            assert!(!factory.has_role(ADMIN, accounts.bob));
+
+            assert!(factory.grant_role(ADMIN, accounts.bob).is_ok());
+            assert!(factory.has_role(ADMIN, accounts.bob));
+
+            assert!(factory.revoke_role(ADMIN, accounts.bob).is_ok());
+            assert!(!factory.has_role(ADMIN, accounts.bob));
+
+            // Non admin cannot grant roles
+            set_sender(accounts.bob);
+            assert_eq!(
+                factory.grant_role(ADMIN, accounts.bob),
+                Err(Error::MissingRole)
+            );
+        }
+
+        /// Test that `set_price` emits a `PriceChanged` event.
+        #[ink::test]
+        fn set_price_emits_event() {
+            let accounts = default_accounts();
+            let mut factory = Factory::new([0x0; 32].into(), 1000);
+            assert!(factory.set_price(100).is_ok());
+
+            let emitted_events = test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 1);
+            assert_price_changed_event(&emitted_events[0], accounts.alice, 1, 100);
+        }
+
+        /// Test error BadMintValue when amount is zero.
+        #[ink::test]
+        fn zero_amount_fails() {
+            let mut factory = Factory::new([0x1; 32].into(), 1000);
+            assert_eq!(factory.mint(0), Err(Error::BadMintValue));
+        }
+
+        /// Test error CollectionFull when amount would exceed max_supply.
+        #[ink::test]
+        fn collection_full_fails() {
+            let mut factory = Factory::new([0x1; 32].into(), 10);
+            assert!(factory.set_price(0).is_ok());
+            assert_eq!(factory.mint(11), Err(Error::CollectionFull));
+        }
+
+        /// `token_contract` is not a deployed contract in the off-chain test
+        /// environment, so the cross-contract `PSP34::mint` call fails and
+        /// `last_token_id` must not advance. See the e2e test for the success
+        /// path against a real `Token` contract.
+        #[ink::test]
+        fn mint_fails_when_cross_contract_call_fails() {
+            let mut factory = Factory::new([0x1; 32].into(), 1000);
+            assert!(factory.set_price(0).is_ok());
+            assert_eq!(factory.mint(50), Err(Error::MintFailed));
+            assert_eq!(factory.last_token_id, 0);
+        }
+
+        fn assert_price_changed_event(
+            event: &test::EmittedEvent,
+            expected_by: AccountId,
+            expected_old_price: Balance,
+            expected_new_price: Balance,
+        ) {
+            type Event = <Factory as ::ink::reflect::ContractEventBase>::Type;
+            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+                .expect("encountered invalid contract event data buffer");
+            if let Event::PriceChanged(PriceChanged {
+                by,
+                old_price,
+                new_price,
+            }) = decoded_event
+            {
+                assert_eq!(by, expected_by);
+                assert_eq!(old_price, expected_old_price);
+                assert_eq!(new_price, expected_new_price);
+            } else {
+                panic!("encountered unexpected event kind: expected a PriceChanged event")
+            }
         }
 
         fn default_accounts() -> test::DefaultAccounts<ink::env::DefaultEnvironment> {
@@ -155,7 +412,7 @@ mod factory {
 
         type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-        const AMOUNT: Balance = 100;
+        const AMOUNT: u64 = 1;
 
         fn get_alice_account_id() -> AccountId {
             let alice = ink_e2e::alice::<ink_e2e::PolkadotConfig>();
@@ -180,7 +437,7 @@ mod factory {
                 .account_id;
 
             // Instantiate factory contract
-            let factory_constructor = FactoryRef::new(token_account_id);
+            let factory_constructor = FactoryRef::new(token_account_id, 1_000_000);
             let factory_account_id = client
                 .instantiate("factory", &ink_e2e::alice(), factory_constructor, 0, None)
                 .await
@@ -8,6 +8,17 @@ mod manicminter {
         call::{build_call, ExecutionInput, Selector},
         DefaultEnvironment,
     };
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    /// Identifier of an access-control role, e.g. `ADMIN` or `MINTER`.
+    pub type RoleType = u32;
+
+    /// May grant/revoke roles and set the mint price.
+    pub const ADMIN: RoleType = ink::selector_id!("ADMIN");
+    /// May call `manic_mint` when the contract is `permissioned`.
+    pub const MINTER: RoleType = ink::selector_id!("MINTER");
+
     #[ink(storage)]
     pub struct ManicMinter {
         /// Contract owner
@@ -16,6 +27,62 @@ mod manicminter {
         token_contract: AccountId,
         /// Minting price. Caller must pay this price to mint one new token from Token contract
         price: Balance,
+        /// Maximum number of tokens that can ever be minted through this contract
+        max_supply: Balance,
+        /// Number of tokens minted so far
+        total_minted: Balance,
+        /// Roles granted to accounts
+        roles: Mapping<(RoleType, AccountId), ()>,
+        /// When `true`, only accounts holding `MINTER` may call `manic_mint`
+        permissioned: bool,
+        /// Account allowed to respond to queued price-update requests
+        attestor: AccountId,
+        /// Ids of pending price-update requests, oldest first
+        request_queue: Vec<u32>,
+        /// Next id to hand out from `request_price_update`
+        next_request_id: u32,
+    }
+
+    /// Emitted when tokens are minted through [`Minting::manic_mint`].
+    #[ink(event)]
+    pub struct Minted {
+        /// Recipient of the minted tokens
+        #[ink(topic)]
+        to: AccountId,
+        /// Number of tokens minted
+        amount: Balance,
+        /// Amount of native token actually paid for the mint
+        paid: Balance,
+    }
+
+    /// Emitted when the mint price is changed through [`Minting::set_price`].
+    #[ink(event)]
+    pub struct PriceChanged {
+        /// Caller who changed the price
+        #[ink(topic)]
+        by: AccountId,
+        /// Previous price
+        old_price: Balance,
+        /// New price
+        new_price: Balance,
+    }
+
+    /// Emitted when a price-update request is queued through `request_price_update`.
+    #[ink(event)]
+    pub struct PriceRequested {
+        /// Id of the queued request
+        #[ink(topic)]
+        id: u32,
+    }
+
+    /// Emitted when the attestor resolves a queued price-update request.
+    #[ink(event)]
+    pub struct PriceUpdated {
+        /// Id of the resolved request
+        #[ink(topic)]
+        id: u32,
+        /// Price reported by the attestor
+        new_price: Balance,
     }
 
     /// The ManicMinter error types.
@@ -30,6 +97,16 @@ mod manicminter {
         ContractNotSet,
         /// Returned if multiplication of price and amount overflows
         OverFlow,
+        /// Returned if minting `amount` tokens would exceed `max_supply`
+        CollectionFull,
+        /// Returned if the requested withdrawal amount exceeds the contract balance
+        InsufficientBalance,
+        /// Returned if the caller does not hold the role required for the call
+        MissingRole,
+        /// Returned if `respond` is called with a request id that is not the head of the queue
+        InvalidRequestId,
+        /// Returned if the cross-contract call to `PSP22Mintable::mint` failed
+        MintFailed,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
@@ -51,12 +128,127 @@ mod manicminter {
 
     impl ManicMinter {
         #[ink(constructor)]
-        pub fn new(contract_acc: AccountId) -> Self {
+        pub fn new(
+            contract_acc: AccountId,
+            max_supply: Balance,
+            permissioned: bool,
+            attestor: AccountId,
+        ) -> Self {
+            let caller = Self::env().caller();
+            let mut roles = Mapping::default();
+            roles.insert((ADMIN, caller), &());
+            roles.insert((MINTER, caller), &());
             Self {
-                owner: Self::env().caller(),
+                owner: caller,
                 token_contract: contract_acc,
                 price: 0,
+                max_supply,
+                total_minted: 0,
+                roles,
+                permissioned,
+                attestor,
+                request_queue: Vec::new(),
+                next_request_id: 0,
+            }
+        }
+
+        /// Returns whether `account` holds `role`.
+        #[ink(message)]
+        pub fn has_role(&self, role: RoleType, account: AccountId) -> bool {
+            self.roles.contains((role, account))
+        }
+
+        /// Grant `role` to `account`. Callable only by an `ADMIN`.
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: RoleType, account: AccountId) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            self.roles.insert((role, account), &());
+            Ok(())
+        }
+
+        /// Revoke `role` from `account`. Callable only by an `ADMIN`.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: RoleType, account: AccountId) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            self.roles.remove((role, account));
+            Ok(())
+        }
+
+        /// Queue a new price-update request for the `attestor` to pick up.
+        #[ink(message)]
+        pub fn request_price_update(&mut self) -> Result<()> {
+            let id = self.next_request_id;
+            self.next_request_id += 1;
+            self.request_queue.push(id);
+            self.env().emit_event(PriceRequested { id });
+            Ok(())
+        }
+
+        /// Resolve the queued request at the head of the queue. Callable only by the `attestor`.
+        #[ink(message)]
+        pub fn respond(&mut self, request_id: u32, new_price: Balance) -> Result<()> {
+            ensure!(self.env().caller() == self.attestor, Error::NotOwner);
+            ensure!(
+                self.request_queue.first() == Some(&request_id),
+                Error::InvalidRequestId
+            );
+            self.request_queue.remove(0);
+            self.price = new_price;
+            self.env().emit_event(PriceUpdated {
+                id: request_id,
+                new_price,
+            });
+            Ok(())
+        }
+
+        /// Checks that `amount` is non-zero and that minting it does not exceed `max_supply`.
+        fn check_amount(&self, amount: Balance) -> Result<()> {
+            ensure!(amount != 0, Error::BadMintValue);
+            ensure!(
+                self.total_minted.checked_add(amount).ok_or(Error::OverFlow)? <= self.max_supply,
+                Error::CollectionFull
+            );
+            Ok(())
+        }
+
+        /// Checks that `transferred` covers `price * amount`, refunds any excess to the caller
+        /// and returns the amount actually owed (`price * amount`).
+        fn check_value(&self, transferred: Balance, amount: Balance) -> Result<Balance> {
+            let required = self.price.checked_mul(amount).ok_or(Error::OverFlow)?;
+            ensure!(transferred >= required, Error::BadMintValue);
+
+            let excess = transferred - required;
+            if excess > 0 {
+                let caller = self.env().caller();
+                if self.env().transfer(caller, excess).is_err() {
+                    ink::env::debug_println!("refund of excess {} to {:?} failed", excess, caller);
+                }
             }
+            Ok(required)
+        }
+
+        /// Withdraw `amount` of the contract's native token balance to the owner.
+        /// Callable by any `ADMIN`, not just `owner`, so this privilege can be
+        /// delegated the same way the rest of the role subsystem is.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            ensure!(
+                amount <= self.env().balance(),
+                Error::InsufficientBalance
+            );
+            self.env()
+                .transfer(self.owner, amount)
+                .map_err(|_| Error::InsufficientBalance)
+        }
+
+        /// Terminate the contract, sweeping the entire remaining balance to the owner.
+        /// Callable by any `ADMIN`, not just `owner`, so this privilege can be
+        /// delegated the same way the rest of the role subsystem is.
+        #[ink(message)]
+        pub fn terminate(&mut self) -> Result<()> {
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            self.env().terminate_contract(self.owner)
         }
     }
 
@@ -68,25 +260,14 @@ mod manicminter {
                 self.token_contract != AccountId::from([0x0; 32]),
                 Error::ContractNotSet
             );
-            if let Some(value) = (amount as u128).checked_mul(self.price) {
-                let transferred_value = self.env().transferred_value();
-                if transferred_value != value {
-                    return Err(Error::BadMintValue);
-                }
-            }
-            match (amount as u128).checked_mul(self.price) {
-                Some(value) => {
-                    let transferred_value = self.env().transferred_value();
-                    if transferred_value != value {
-                        return Err(Error::BadMintValue);
-                    }
-                }
-                None => {
-                    return Err(Error::OverFlow);
-                }
-            }
+            ensure!(
+                !self.permissioned || self.has_role(MINTER, caller),
+                Error::MissingRole
+            );
+            self.check_amount(amount)?;
+            let paid = self.check_value(self.env().transferred_value(), amount)?;
 
-            let _mint_result = build_call::<DefaultEnvironment>()
+            let mint_result = build_call::<DefaultEnvironment>()
                 .call(self.token_contract)
                 .gas_limit(5000000000)
                 .exec_input(
@@ -96,14 +277,28 @@ mod manicminter {
                 )
                 .returns::<()>()
                 .try_invoke();
-            ink::env::debug_println!("mint_result: {:?}", _mint_result);
+            ink::env::debug_println!("mint_result: {:?}", mint_result);
+            ensure!(matches!(mint_result, Ok(Ok(()))), Error::MintFailed);
+
+            self.total_minted += amount;
+            self.env().emit_event(Minted {
+                to: caller,
+                amount,
+                paid,
+            });
             Ok(())
         }
 
         #[ink(message)]
         fn set_price(&mut self, price: Balance) -> Result<()> {
-            ensure!(self.env().caller() == self.owner, Error::NotOwner);
+            ensure!(self.has_role(ADMIN, self.env().caller()), Error::MissingRole);
+            let old_price = self.price;
             self.price = price;
+            self.env().emit_event(PriceChanged {
+                by: self.env().caller(),
+                old_price,
+                new_price: price,
+            });
             Ok(())
         }
 
@@ -122,7 +317,7 @@ mod manicminter {
         /// Test error ContractNotSet.
         #[ink::test]
         fn contract_not_set_works() {
-            let mut manicminter = ManicMinter::new([0x0; 32].into());
+            let mut manicminter = ManicMinter::new([0x0; 32].into(), 1000, false, [0x9; 32].into());
             assert_eq!(manicminter.manic_mint(50), Err(Error::ContractNotSet));
         }
 
@@ -130,13 +325,187 @@ mod manicminter {
         #[ink::test]
         fn set_price_works() {
             let accounts = default_accounts();
-            let mut manicminter = ManicMinter::new([0x0; 32].into());
+            let mut manicminter = ManicMinter::new([0x0; 32].into(), 1000, false, [0x9; 32].into());
             assert!(manicminter.set_price(100).is_ok());
             assert_eq!(manicminter.get_price(), 100);
 
-            // Non owner fails to set price
+            // Non admin fails to set price
+            set_sender(accounts.bob);
+            assert_eq!(manicminter.set_price(100), Err(Error::MissingRole));
+        }
+
+        /// Test error BadMintValue when amount is zero.
+        #[ink::test]
+        fn zero_amount_fails() {
+            let mut manicminter = ManicMinter::new([0x1; 32].into(), 1000, false, [0x9; 32].into());
+            assert_eq!(manicminter.manic_mint(0), Err(Error::BadMintValue));
+        }
+
+        /// Test withdrawing funds as the owner.
+        #[ink::test]
+        fn withdraw_works() {
+            let accounts = default_accounts();
+            let mut manicminter = ManicMinter::new([0x0; 32].into(), 1000, false, [0x9; 32].into());
+            let contract_acc = ink::env::account_id::<Environment>();
+            test::set_account_balance::<Environment>(contract_acc, 100);
+
+            assert_eq!(
+                manicminter.withdraw(200),
+                Err(Error::InsufficientBalance)
+            );
+            assert!(manicminter.withdraw(100).is_ok());
+
+            // Non admin fails to withdraw
+            set_sender(accounts.bob);
+            assert_eq!(manicminter.withdraw(0), Err(Error::MissingRole));
+        }
+
+        /// Test that `terminate` sweeps the balance to the owner and removes the contract.
+        #[ink::test]
+        fn terminate_works() {
+            let accounts = default_accounts();
+            let mut manicminter = ManicMinter::new([0x0; 32].into(), 1000, false, [0x9; 32].into());
+            let contract_acc = ink::env::account_id::<Environment>();
+            test::set_account_balance::<Environment>(contract_acc, 100);
+
+            test::assert_contract_termination::<Environment, _>(
+                move || {
+                    manicminter.terminate().unwrap();
+                },
+                accounts.alice,
+                100,
+            );
+        }
+
+        /// Test that `set_price` emits a `PriceChanged` event.
+        #[ink::test]
+        fn set_price_emits_event() {
+            let accounts = default_accounts();
+            let mut manicminter = ManicMinter::new([0x0; 32].into(), 1000, false, [0x9; 32].into());
+            assert!(manicminter.set_price(100).is_ok());
+
+            let emitted_events = test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 1);
+            assert_price_changed_event(&emitted_events[0], accounts.alice, 0, 100);
+        }
+
+        /// `token_contract` is not a deployed contract in the off-chain test
+        /// environment, so the cross-contract `PSP22Mintable::mint` call fails
+        /// and no `Minted` event should be emitted. See the e2e test for the
+        /// success path against a real `Token` contract.
+        #[ink::test]
+        fn manic_mint_fails_when_cross_contract_call_fails() {
+            let mut manicminter = ManicMinter::new([0x1; 32].into(), 1000, false, [0x9; 32].into());
+            assert_eq!(manicminter.manic_mint(50), Err(Error::MintFailed));
+            assert_eq!(manicminter.total_minted, 0);
+            assert!(test::recorded_events().collect::<Vec<_>>().is_empty());
+        }
+
+        fn assert_price_changed_event(
+            event: &test::EmittedEvent,
+            expected_by: AccountId,
+            expected_old_price: Balance,
+            expected_new_price: Balance,
+        ) {
+            type Event = <ManicMinter as ::ink::reflect::ContractEventBase>::Type;
+            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+                .expect("encountered invalid contract event data buffer");
+            if let Event::PriceChanged(PriceChanged {
+                by,
+                old_price,
+                new_price,
+            }) = decoded_event
+            {
+                assert_eq!(by, expected_by);
+                assert_eq!(old_price, expected_old_price);
+                assert_eq!(new_price, expected_new_price);
+            } else {
+                panic!("encountered unexpected event kind: expected a PriceChanged event")
+            }
+        }
+
+        /// Test error CollectionFull when amount would exceed max_supply.
+        #[ink::test]
+        fn collection_full_fails() {
+            let mut manicminter = ManicMinter::new([0x1; 32].into(), 10, false, [0x9; 32].into());
+            assert_eq!(manicminter.manic_mint(11), Err(Error::CollectionFull));
+        }
+
+        /// Test granting and revoking roles.
+        #[ink::test]
+        fn grant_and_revoke_role_works() {
+            let accounts = default_accounts();
+            let mut manicminter = ManicMinter::new([0x0; 32].into(), 1000, false, [0x9; 32].into());
+            assert!(manicminter.has_role(ADMIN, accounts.alice));
+            assert!(!manicminter.has_role(MINTER, accounts.bob));
+
+            assert!(manicminter.grant_role(MINTER, accounts.bob).is_ok());
+            assert!(manicminter.has_role(MINTER, accounts.bob));
+
+            assert!(manicminter.revoke_role(MINTER, accounts.bob).is_ok());
+            assert!(!manicminter.has_role(MINTER, accounts.bob));
+
+            // Non admin cannot grant roles
+            set_sender(accounts.bob);
+            assert_eq!(
+                manicminter.grant_role(MINTER, accounts.bob),
+                Err(Error::MissingRole)
+            );
+        }
+
+        /// Test that a permissioned contract rejects mints from non-MINTER accounts.
+        #[ink::test]
+        fn permissioned_mint_requires_minter_role() {
+            let accounts = default_accounts();
+            let mut manicminter = ManicMinter::new([0x1; 32].into(), 1000, true, [0x9; 32].into());
+
+            set_sender(accounts.bob);
+            assert_eq!(manicminter.manic_mint(10), Err(Error::MissingRole));
+
+            set_sender(accounts.alice);
+            assert!(manicminter.grant_role(MINTER, accounts.bob).is_ok());
+
             set_sender(accounts.bob);
-            assert_eq!(manicminter.set_price(100), Err(Error::NotOwner));
+            // Role check now passes; the call still fails because
+            // `token_contract` is not a deployed contract in this environment.
+            assert_eq!(manicminter.manic_mint(10), Err(Error::MintFailed));
+        }
+
+        /// Test the full request/respond price-update flow.
+        #[ink::test]
+        fn oracle_price_update_works() {
+            let accounts = default_accounts();
+            let mut manicminter =
+                ManicMinter::new([0x0; 32].into(), 1000, false, accounts.eve);
+
+            assert!(manicminter.request_price_update().is_ok());
+            assert!(manicminter.request_price_update().is_ok());
+
+            // Only the attestor may respond
+            assert_eq!(
+                manicminter.respond(0, 42),
+                Err(Error::NotOwner)
+            );
+
+            set_sender(accounts.eve);
+
+            // Out-of-order responses are rejected
+            assert_eq!(
+                manicminter.respond(1, 42),
+                Err(Error::InvalidRequestId)
+            );
+
+            assert!(manicminter.respond(0, 42).is_ok());
+            assert_eq!(manicminter.get_price(), 42);
+
+            // Stale (already-popped) ids are rejected too
+            assert_eq!(
+                manicminter.respond(0, 7),
+                Err(Error::InvalidRequestId)
+            );
+
+            assert!(manicminter.respond(1, 100).is_ok());
+            assert_eq!(manicminter.get_price(), 100);
         }
 
         fn default_accounts() -> test::DefaultAccounts<ink::env::DefaultEnvironment> {
@@ -190,7 +559,8 @@ mod manicminter {
                 .account_id;
 
             // Instantiate manic-minter contract
-            let manic_minter_constructor = ManicMinterRef::new(token_account_id);
+            let manic_minter_constructor =
+                ManicMinterRef::new(token_account_id, 1_000_000, false, get_bob_account_id());
             let manic_minter_account_id = client
                 .instantiate(
                     "manic-minter",